@@ -0,0 +1,38 @@
+//! Pluggable crypto backend.
+//!
+//! The default `openssl` feature links the native OpenSSL library. Enabling `rustls` instead
+//! selects a pure-Rust backend (`p521` + `ecdsa`) with no OpenSSL/native-tls linkage, which makes
+//! the crate easy to build in slim containers and to cross-compile. Both backends expose the same
+//! ES512 operations and the fixed-width 66-byte `r||s` signature encoding.
+
+/// The JWS signing algorithm, selecting both the key type and the signature encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    /// ECDSA with a P-521 key and SHA-512, fixed-width `r||s` encoding.
+    Es512,
+    /// EdDSA with an Ed25519 key, raw 64-byte signature.
+    EdDsa,
+    /// RSASSA-PSS with SHA-512 and MGF1, salt length equal to the digest.
+    Ps512,
+}
+
+impl Algorithm {
+    /// The `alg` value to place in the JWS protected header.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Algorithm::Es512 => "ES512",
+            Algorithm::EdDsa => "EdDSA",
+            Algorithm::Ps512 => "PS512",
+        }
+    }
+}
+
+#[cfg(feature = "openssl")]
+mod openssl;
+#[cfg(feature = "openssl")]
+pub use self::openssl::{SigningKeyInner, VerifyingKeyInner};
+
+#[cfg(all(feature = "rustls", not(feature = "openssl")))]
+mod rustls;
+#[cfg(all(feature = "rustls", not(feature = "openssl")))]
+pub use self::rustls::{SigningKeyInner, VerifyingKeyInner};
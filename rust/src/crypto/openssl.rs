@@ -0,0 +1,169 @@
+//! OpenSSL-backed signing implementation (the default backend).
+use super::Algorithm;
+use crate::base64_decode as base64url_decode;
+use anyhow::Context;
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::ec::{EcGroup, EcKey, EcPoint};
+use openssl::ecdsa::EcdsaSig;
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::{Id, PKey, Private, Public};
+use openssl::rsa::Padding;
+use openssl::sign::{RsaPssSaltlen, Signer};
+
+/// A JWK private key, limited to the fields required to rebuild a P-521 EC key.
+#[derive(serde::Deserialize)]
+struct PrivateJwk {
+    crv: Option<String>,
+    d: String,
+    x: String,
+    y: String,
+}
+
+/// A private key validated against the algorithm it will be used with.
+#[derive(Clone)]
+pub struct SigningKeyInner {
+    algorithm: Algorithm,
+    key: PKey<Private>,
+}
+
+impl SigningKeyInner {
+    /// Parse a PEM-encoded private key and validate that its type matches `algorithm`.
+    pub fn from_pem(pem: &[u8], algorithm: Algorithm) -> Result<Self, anyhow::Error> {
+        let key = PKey::private_key_from_pem(pem)
+            .context("Failed to parse the private key as PEM.")?;
+        Self::from_pkey(key, algorithm)
+    }
+
+    /// Parse a DER-encoded private key and validate that its type matches `algorithm`.
+    pub fn from_der(der: &[u8], algorithm: Algorithm) -> Result<Self, anyhow::Error> {
+        let key = PKey::private_key_from_der(der)
+            .context("Failed to parse the private key as DER.")?;
+        Self::from_pkey(key, algorithm)
+    }
+
+    /// Parse a JWK private key. Only P-521 EC keys (ES512) are supported in JWK form.
+    pub fn from_jwk(jwk: &[u8], algorithm: Algorithm) -> Result<Self, anyhow::Error> {
+        if algorithm != Algorithm::Es512 {
+            return Err(anyhow::anyhow!(
+                "JWK keys are only supported for ES512 (P-521)."
+            ));
+        }
+        let jwk: PrivateJwk =
+            serde_json::from_slice(jwk).context("Failed to parse the private key as a JWK.")?;
+        if jwk.crv.as_deref() != Some("P-521") {
+            return Err(anyhow::anyhow!(
+                "The JWK curve must be P-521 (SECP521R1) to sign using ES512."
+            ));
+        }
+        let group = EcGroup::from_curve_name(Nid::SECP521R1)?;
+        let d = BigNum::from_slice(&base64url_decode(&jwk.d)?)?;
+        let x = BigNum::from_slice(&base64url_decode(&jwk.x)?)?;
+        let y = BigNum::from_slice(&base64url_decode(&jwk.y)?)?;
+        let mut ctx = BigNumContext::new()?;
+        let mut public_point = EcPoint::new(&group)?;
+        public_point.set_affine_coordinates_gfp(&group, &x, &y, &mut ctx)?;
+        let ec = EcKey::from_private_components(&group, &d, &public_point)
+            .context("Failed to rebuild the EC key from the JWK components.")?;
+        ec.check_key().context("Key verification failed")?;
+        let key = PKey::from_ec_key(ec)?;
+        Ok(Self {
+            algorithm,
+            key,
+        })
+    }
+
+    /// Validate a parsed key against `algorithm` and wrap it.
+    fn from_pkey(key: PKey<Private>, algorithm: Algorithm) -> Result<Self, anyhow::Error> {
+        match algorithm {
+            Algorithm::Es512 => {
+                let ec = key
+                    .ec_key()
+                    .context("ES512 requires an Elliptic Curve private key.")?;
+                ec.check_key().context("Key verification failed")?;
+                if ec.group().curve_name() != Some(Nid::SECP521R1) {
+                    return Err(anyhow::anyhow!(
+                        "The underlying elliptic curve must be P-521 to sign using ES512."
+                    ));
+                }
+            }
+            Algorithm::EdDsa => {
+                if key.id() != Id::ED25519 {
+                    return Err(anyhow::anyhow!("EdDSA requires an Ed25519 private key."));
+                }
+            }
+            Algorithm::Ps512 => {
+                key.rsa().context("PS512 requires an RSA private key.")?;
+            }
+        }
+        Ok(Self { algorithm, key })
+    }
+
+    /// Sign the payload, returning the raw signature bytes in the encoding required by the `alg`.
+    pub fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        match self.algorithm {
+            Algorithm::Es512 => self.sign_es512(payload),
+            Algorithm::EdDsa => {
+                // Ed25519 is a one-shot, prehash-free signature yielding raw 64 bytes.
+                let mut signer = Signer::new_without_digest(&self.key)?;
+                Ok(signer.sign_oneshot_to_vec(payload)?)
+            }
+            Algorithm::Ps512 => {
+                let mut signer = Signer::new(MessageDigest::sha512(), &self.key)?;
+                signer.set_rsa_padding(Padding::PKCS1_PSS)?;
+                signer.set_rsa_mgf1_md(MessageDigest::sha512())?;
+                signer.set_rsa_pss_saltlen(RsaPssSaltlen::DIGEST_LENGTH)?;
+                signer.update(payload)?;
+                Ok(signer.sign_to_vec()?)
+            }
+        }
+    }
+
+    /// Sign the payload and return the fixed-width 66-byte `r||s` signature (132 bytes).
+    fn sign_es512(&self, payload: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let ec = self.key.ec_key()?;
+        let hash = openssl::hash::hash(MessageDigest::sha512(), payload)?;
+        let structured_signature = EcdsaSig::sign(&hash, &ec)?;
+
+        let r = structured_signature.r().to_vec();
+        let s = structured_signature.s().to_vec();
+        let mut signature_bytes: Vec<u8> = Vec::new();
+        // Padding to fixed length
+        signature_bytes.extend(std::iter::repeat(0x00).take(66 - r.len()));
+        signature_bytes.extend(r);
+        // Padding to fixed length
+        signature_bytes.extend(std::iter::repeat(0x00).take(66 - s.len()));
+        signature_bytes.extend(s);
+
+        Ok(signature_bytes)
+    }
+}
+
+/// A validated P-521 public key.
+#[derive(Clone)]
+pub struct VerifyingKeyInner {
+    key: EcKey<Public>,
+}
+
+impl VerifyingKeyInner {
+    /// Build a P-521 public key from its big-endian `x`/`y` affine coordinates.
+    pub fn from_coordinates(x: &[u8], y: &[u8]) -> Result<Self, anyhow::Error> {
+        let group = EcGroup::from_curve_name(Nid::SECP521R1)?;
+        let x = BigNum::from_slice(x)?;
+        let y = BigNum::from_slice(y)?;
+        let key = EcKey::from_public_key_affine_coordinates(&group, &x, &y)
+            .context("Failed to build a public key from the JWK coordinates.")?;
+        key.check_key().context("Key verification failed")?;
+        Ok(Self { key })
+    }
+
+    /// Verify a fixed-width 66-byte `r||s` signature (132 bytes) over the payload.
+    pub fn verify_es512(&self, payload: &[u8], signature: &[u8]) -> Result<bool, anyhow::Error> {
+        let r = BigNum::from_slice(&signature[..66])?;
+        let s = BigNum::from_slice(&signature[66..])?;
+        let structured_signature = EcdsaSig::from_private_components(r, s)?;
+
+        let hash = openssl::hash::hash(MessageDigest::sha512(), payload)?;
+        Ok(structured_signature.verify(&hash, &self.key)?)
+    }
+}
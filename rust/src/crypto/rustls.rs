@@ -0,0 +1,166 @@
+//! Pure-Rust signing implementation, free of any OpenSSL linkage.
+//!
+//! ES512 is backed by `p521` + `ecdsa`, EdDSA by `ed25519-dalek`, and PS512 by `rsa`.
+use super::Algorithm;
+use anyhow::Context;
+use ed25519_dalek::pkcs8::DecodePrivateKey as _;
+use ed25519_dalek::Signer as _;
+use p521::ecdsa::signature::{Signer as _, Verifier as _};
+use p521::ecdsa::{Signature, SigningKey, VerifyingKey};
+use p521::elliptic_curve::generic_array::GenericArray;
+use p521::elliptic_curve::pkcs8::DecodePrivateKey as _;
+use p521::sec1::DecodeEcPrivateKey as _;
+use p521::{EncodedPoint, FieldBytes, SecretKey};
+use rand_core::OsRng;
+use rsa::pkcs1::DecodeRsaPrivateKey as _;
+use rsa::pkcs8::DecodePrivateKey as _;
+use rsa::signature::RandomizedSigner as _;
+use sha2::Sha512;
+
+/// A JWK private key, limited to the fields required to rebuild a P-521 EC key.
+#[derive(serde::Deserialize)]
+struct PrivateJwk {
+    crv: Option<String>,
+    d: String,
+}
+
+/// A private key validated against the algorithm it will be used with.
+#[derive(Clone)]
+pub struct SigningKeyInner {
+    key: Key,
+}
+
+#[derive(Clone)]
+enum Key {
+    Es512(SigningKey),
+    EdDsa(ed25519_dalek::SigningKey),
+    Ps512(rsa::RsaPrivateKey),
+}
+
+impl SigningKeyInner {
+    /// Parse a PEM-encoded private key and validate that its type matches `algorithm`.
+    pub fn from_pem(pem: &[u8], algorithm: Algorithm) -> Result<Self, anyhow::Error> {
+        let pem = std::str::from_utf8(pem).context("The private key PEM is not valid UTF-8.")?;
+        let key = match algorithm {
+            Algorithm::Es512 => {
+                let secret = SecretKey::from_pkcs8_pem(pem)
+                    .or_else(|_| SecretKey::from_sec1_pem(pem))
+                    .context("Failed to parse the private key as a P-521 PEM key.")?;
+                Key::Es512(SigningKey::from(&secret))
+            }
+            Algorithm::EdDsa => Key::EdDsa(
+                ed25519_dalek::SigningKey::from_pkcs8_pem(pem)
+                    .context("EdDSA requires an Ed25519 private key.")?,
+            ),
+            Algorithm::Ps512 => {
+                let key = rsa::RsaPrivateKey::from_pkcs8_pem(pem)
+                    .or_else(|_| rsa::RsaPrivateKey::from_pkcs1_pem(pem))
+                    .context("PS512 requires an RSA private key.")?;
+                Key::Ps512(key)
+            }
+        };
+        Ok(Self { key })
+    }
+
+    /// Parse a DER-encoded private key and validate that its type matches `algorithm`.
+    pub fn from_der(der: &[u8], algorithm: Algorithm) -> Result<Self, anyhow::Error> {
+        let key = match algorithm {
+            Algorithm::Es512 => {
+                let secret = SecretKey::from_pkcs8_der(der)
+                    .or_else(|_| SecretKey::from_sec1_der(der))
+                    .context("Failed to parse the private key as a P-521 DER key.")?;
+                Key::Es512(SigningKey::from(&secret))
+            }
+            Algorithm::EdDsa => Key::EdDsa(
+                ed25519_dalek::SigningKey::from_pkcs8_der(der)
+                    .context("EdDSA requires an Ed25519 private key.")?,
+            ),
+            Algorithm::Ps512 => {
+                let key = rsa::RsaPrivateKey::from_pkcs8_der(der)
+                    .or_else(|_| rsa::RsaPrivateKey::from_pkcs1_der(der))
+                    .context("PS512 requires an RSA private key.")?;
+                Key::Ps512(key)
+            }
+        };
+        Ok(Self { key })
+    }
+
+    /// Parse a JWK private key. Only P-521 EC keys (ES512) are supported in JWK form.
+    pub fn from_jwk(jwk: &[u8], algorithm: Algorithm) -> Result<Self, anyhow::Error> {
+        if algorithm != Algorithm::Es512 {
+            return Err(anyhow::anyhow!(
+                "JWK keys are only supported for ES512 (P-521)."
+            ));
+        }
+        let jwk: PrivateJwk =
+            serde_json::from_slice(jwk).context("Failed to parse the private key as a JWK.")?;
+        if jwk.crv.as_deref() != Some("P-521") {
+            return Err(anyhow::anyhow!(
+                "The JWK curve must be P-521 (SECP521R1) to sign using ES512."
+            ));
+        }
+        let d = left_pad(&crate::base64_decode(&jwk.d)?)?;
+        let secret = SecretKey::from_bytes(&d)
+            .context("Failed to rebuild the EC key from the JWK `d` scalar.")?;
+        Ok(Self {
+            key: Key::Es512(SigningKey::from(&secret)),
+        })
+    }
+
+    /// Sign the payload, returning the raw signature bytes in the encoding required by the `alg`.
+    pub fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        match &self.key {
+            // `ecdsa` hashes with SHA-512 for P-521 and `Signature::to_bytes` already emits the
+            // zero-padded `r||s` form, so no manual padding is required.
+            Key::Es512(key) => {
+                let signature: Signature = key.sign(payload);
+                Ok(signature.to_bytes().to_vec())
+            }
+            Key::EdDsa(key) => Ok(key.sign(payload).to_bytes().to_vec()),
+            Key::Ps512(key) => {
+                // `rsa`'s PSS signer defaults the salt length to the digest output size.
+                let signing_key = rsa::pss::SigningKey::<Sha512>::new(key.clone());
+                let signature = signing_key.sign_with_rng(&mut OsRng, payload);
+                Ok(signature.to_vec())
+            }
+        }
+    }
+}
+
+/// A validated P-521 public key.
+#[derive(Clone)]
+pub struct VerifyingKeyInner {
+    key: VerifyingKey,
+}
+
+impl VerifyingKeyInner {
+    /// Build a P-521 public key from its big-endian `x`/`y` affine coordinates.
+    pub fn from_coordinates(x: &[u8], y: &[u8]) -> Result<Self, anyhow::Error> {
+        // JWK coordinates may drop leading zero bytes; left-pad each to the 66-byte field width.
+        let x = left_pad(x)?;
+        let y = left_pad(y)?;
+        let point = EncodedPoint::from_affine_coordinates(&x, &y, false);
+        let key = VerifyingKey::from_encoded_point(&point)
+            .context("Failed to build a public key from the JWK coordinates.")?;
+        Ok(Self { key })
+    }
+
+    /// Verify a fixed-width 66-byte `r||s` signature (132 bytes) over the payload.
+    pub fn verify_es512(&self, payload: &[u8], signature: &[u8]) -> Result<bool, anyhow::Error> {
+        let signature = Signature::from_slice(signature)
+            .context("Failed to parse the signature as a P-521 `r||s` pair.")?;
+        Ok(self.key.verify(payload, &signature).is_ok())
+    }
+}
+
+/// Left-pad a big-endian coordinate to the 66-byte P-521 field width.
+fn left_pad(bytes: &[u8]) -> Result<FieldBytes, anyhow::Error> {
+    if bytes.len() > 66 {
+        return Err(anyhow::anyhow!(
+            "A P-521 coordinate must not exceed 66 bytes."
+        ));
+    }
+    let mut field_bytes = GenericArray::default();
+    field_bytes[66 - bytes.len()..].copy_from_slice(bytes);
+    Ok(field_bytes)
+}
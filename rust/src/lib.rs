@@ -0,0 +1,297 @@
+//! Signing and verification primitives for TrueLayer's Payouts API.
+//!
+//! The entry points are [`SigningKey`]/[`VerifyingKey`], which parse and validate a key once so
+//! it can be reused across many signatures (a P-521, Ed25519 or RSA private key for signing, a
+//! P-521 public key for verification), and the [`Signer`] builder, which produces a
+//! [`Jws`] exposing both its compact and detached serializations.
+use anyhow::Context;
+use base64::URL_SAFE_NO_PAD;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+mod crypto;
+
+pub use crypto::Algorithm;
+use crypto::{SigningKeyInner, VerifyingKeyInner};
+
+/// The on-disk encoding of a private key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyFormat {
+    /// A PEM-encoded key (SEC1 or PKCS#8).
+    Pem,
+    /// A DER-encoded key.
+    Der,
+    /// A JSON Web Key object.
+    Jwk,
+    /// Try each supported format in turn.
+    Auto,
+}
+
+/// A private key validated against its signing algorithm, parsed once and reused.
+#[derive(Clone)]
+pub struct SigningKey {
+    algorithm: Algorithm,
+    inner: SigningKeyInner,
+}
+
+impl SigningKey {
+    /// Parse a PEM-encoded private key, validating that its type matches `algorithm`.
+    ///
+    /// The key is parsed and validated here a single time; the resulting [`SigningKey`] can then
+    /// sign many payloads without repeating that work.
+    pub fn from_pem(pem: &[u8], algorithm: Algorithm) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            algorithm,
+            inner: SigningKeyInner::from_pem(pem, algorithm)?,
+        })
+    }
+
+    /// Parse a private key in the given format, validating that its type matches `algorithm`.
+    ///
+    /// With [`KeyFormat::Auto`] each supported format is tried in turn (PEM, then DER, then JWK)
+    /// and, on failure, the error lists every format that was attempted.
+    pub fn from_bytes(
+        bytes: &[u8],
+        algorithm: Algorithm,
+        format: KeyFormat,
+    ) -> Result<Self, anyhow::Error> {
+        let inner = match format {
+            KeyFormat::Pem => SigningKeyInner::from_pem(bytes, algorithm)?,
+            KeyFormat::Der => SigningKeyInner::from_der(bytes, algorithm)?,
+            KeyFormat::Jwk => SigningKeyInner::from_jwk(bytes, algorithm)?,
+            KeyFormat::Auto => {
+                let mut attempts = Vec::new();
+                match SigningKeyInner::from_pem(bytes, algorithm) {
+                    Ok(inner) => return Ok(Self { algorithm, inner }),
+                    Err(e) => attempts.push(format!("PEM: {}", e)),
+                }
+                match SigningKeyInner::from_der(bytes, algorithm) {
+                    Ok(inner) => return Ok(Self { algorithm, inner }),
+                    Err(e) => attempts.push(format!("DER: {}", e)),
+                }
+                match SigningKeyInner::from_jwk(bytes, algorithm) {
+                    Ok(inner) => return Ok(Self { algorithm, inner }),
+                    Err(e) => attempts.push(format!("JWK: {}", e)),
+                }
+                return Err(anyhow::anyhow!(
+                    "Could not parse the private key in any supported format.\n{}",
+                    attempts.join("\n")
+                ));
+            }
+        };
+        Ok(Self { algorithm, inner })
+    }
+
+    /// The algorithm this key signs with.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Sign a payload, returning the base64url-encoded signature in the algorithm's encoding.
+    ///
+    /// Check section A.4 of RFC7515 for the details: https://www.rfc-editor.org/rfc/rfc7515.txt
+    pub fn sign(&self, payload: &[u8]) -> Result<String, anyhow::Error> {
+        Ok(base64_encode(&self.inner.sign(payload)?))
+    }
+}
+
+/// A validated P-521 public key, the verification counterpart to [`SigningKey`].
+#[derive(Clone)]
+pub struct VerifyingKey {
+    inner: VerifyingKeyInner,
+}
+
+impl VerifyingKey {
+    /// Rebuild a P-521 public key from the base64url `x`/`y` coordinates of a JWK.
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, anyhow::Error> {
+        if jwk.crv.as_deref() != Some("P-521") {
+            return Err(anyhow::anyhow!(
+                "The JWK curve must be P-521 (SECP521R1) to verify an ES512 signature."
+            ));
+        }
+        let x = base64_decode(&jwk.x).context("Failed to base64url-decode the JWK `x` coordinate.")?;
+        let y = base64_decode(&jwk.y).context("Failed to base64url-decode the JWK `y` coordinate.")?;
+        Ok(Self {
+            inner: VerifyingKeyInner::from_coordinates(&x, &y)?,
+        })
+    }
+
+    /// Verify an ES512 signature produced by [`SigningKey::sign`].
+    ///
+    /// Reverses the fixed-width encoding: the base64url signature decodes to the two 66-byte
+    /// big-endian halves `r` and `s`, which are checked against the SHA-512 digest of the payload.
+    pub fn verify_es512(&self, payload: &[u8], signature: &str) -> Result<bool, anyhow::Error> {
+        let signature_bytes =
+            base64_decode(signature).context("Failed to base64url-decode the signature.")?;
+        if signature_bytes.len() != 132 {
+            return Err(anyhow::anyhow!(
+                "An ES512 signature must decode to 132 bytes (two 66-byte halves)."
+            ));
+        }
+        self.inner.verify_es512(payload, &signature_bytes)
+    }
+}
+
+/// Builder for a JWS over a payload, signed with the algorithm of its [`SigningKey`].
+pub struct Signer {
+    key: SigningKey,
+    kid: Option<String>,
+    extra_headers: Vec<(String, Value)>,
+}
+
+impl Signer {
+    /// Start a signer backed by the given key.
+    pub fn new(key: SigningKey) -> Self {
+        Self {
+            key,
+            kid: None,
+            extra_headers: Vec::new(),
+        }
+    }
+
+    /// Set the `kid` protected header to the given certificate id.
+    pub fn with_kid(mut self, kid: Uuid) -> Self {
+        self.kid = Some(kid.to_string());
+        self
+    }
+
+    /// Add a custom protected header claim (e.g. `tl_version`, `tl_headers`).
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sign the payload and return the resulting [`Jws`].
+    ///
+    /// Check section A.4 of RFC7515 for the details: https://www.rfc-editor.org/rfc/rfc7515.txt
+    pub fn sign(&self, payload: &str) -> Result<Jws, anyhow::Error> {
+        let mut header = json!({ "alg": self.key.algorithm().as_str() });
+        if let Some(kid) = &self.kid {
+            header["kid"] = json!(kid);
+        }
+        for (name, value) in &self.extra_headers {
+            header[name] = value.clone();
+        }
+
+        let header_b64 = base64_encode(serde_json::to_string(&header)?.as_bytes());
+        let payload_b64 = base64_encode(payload.as_bytes());
+        let signing_input = format!("{}.{}", header_b64, payload_b64);
+        let signature = self.key.sign(signing_input.as_bytes())?;
+
+        Ok(Jws {
+            header_b64,
+            payload_b64,
+            signature,
+        })
+    }
+}
+
+/// A signed JWS, retaining its segments so both serializations are available without re-splitting.
+pub struct Jws {
+    header_b64: String,
+    payload_b64: String,
+    signature: String,
+}
+
+impl Jws {
+    /// The compact serialization: `header.payload.signature`.
+    pub fn compact(&self) -> String {
+        format!("{}.{}.{}", self.header_b64, self.payload_b64, self.signature)
+    }
+
+    /// The detached serialization with the payload omitted: `header..signature`.
+    pub fn detached(&self) -> String {
+        format!("{}..{}", self.header_b64, self.signature)
+    }
+}
+
+/// A single JSON Web Key, limited to the fields required to rebuild a P-521 public key.
+#[derive(serde::Deserialize)]
+pub struct Jwk {
+    pub kid: Option<String>,
+    pub crv: Option<String>,
+    pub x: String,
+    pub y: String,
+}
+
+/// A JSON Web Key set, as published by TrueLayer.
+#[derive(serde::Deserialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+impl JwkSet {
+    /// Find the key matching the given `kid`.
+    pub fn find(&self, kid: &str) -> Option<&Jwk> {
+        self.keys.iter().find(|k| k.kid.as_deref() == Some(kid))
+    }
+}
+
+/// Parse repeatable `Name: value` header arguments, preserving the order they were given in.
+pub fn parse_headers(raw: &[String]) -> Result<Vec<(String, String)>, anyhow::Error> {
+    raw.iter()
+        .map(|entry| {
+            let mut parts = entry.splitn(2, ':');
+            let name = parts
+                .next()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .with_context(|| format!("Invalid header `{}`, expected `Name: value`.", entry))?;
+            let value = parts
+                .next()
+                .map(str::trim)
+                .with_context(|| format!("Invalid header `{}`, expected `Name: value`.", entry))?;
+            Ok((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Select the headers named by `tl_headers` from the presented request, matching names
+/// case-insensitively and preserving the signed order. Errors if any is missing.
+pub fn select_signed_headers(
+    tl_headers: &str,
+    presented: &[(String, String)],
+) -> Result<Vec<(String, String)>, anyhow::Error> {
+    tl_headers
+        .split(',')
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            let (_, value) = presented
+                .iter()
+                .find(|(present, _)| present.eq_ignore_ascii_case(name))
+                .with_context(|| {
+                    format!("The signature references header `{}`, absent from the request.", name)
+                })?;
+            // Re-emit the signed name recorded in `tl_headers`, not the presented casing, so both
+            // ends canonicalize to the same bytes regardless of how the verifier cased the header.
+            Ok((name.to_string(), value.clone()))
+        })
+        .collect()
+}
+
+/// Build the canonical signing payload binding the HTTP method, path, selected headers and body.
+///
+/// The layout is `<METHOD> <PATH>\n<Header-Name>: <value>\n...\n<body>`.
+pub fn build_request_payload(
+    method: &str,
+    path: &str,
+    headers: &[(String, String)],
+    body: &str,
+) -> String {
+    let mut payload = format!("{} {}\n", method.to_uppercase(), path);
+    for (name, value) in headers {
+        payload.push_str(&format!("{}: {}\n", name, value));
+    }
+    payload.push_str(body);
+    payload
+}
+
+/// Base64 encoding according to RFC7515 - see `Base64url` in section 2.
+pub fn base64_encode(payload: &[u8]) -> String {
+    base64::encode_config(payload, URL_SAFE_NO_PAD)
+}
+
+/// Base64 decoding according to RFC7515 - see `Base64url` in section 2.
+pub fn base64_decode(payload: &str) -> Result<Vec<u8>, anyhow::Error> {
+    base64::decode_config(payload, URL_SAFE_NO_PAD).context("Failed to base64url-decode the input.")
+}